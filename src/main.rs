@@ -1,45 +1,182 @@
+mod network;
+
 use bevy::{diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin}, prelude::*};
 use bevy_egui::{EguiContext, EguiPlugin, egui::Pos2};
 use bevy_egui::egui;
+use bevy_ggrs::{GGRSPlugin, Rollback, RollbackIdProvider, Session};
+use bevy_kira_audio::{Audio, AudioPlugin, AudioSource};
+use network::{
+    GGRSConfig, LocalHandle, NetworkConfig, INPUT_CONFIRM, INPUT_DOWN, INPUT_PAUSE,
+    INPUT_TOGGLE_AUTO, INPUT_UP,
+};
 
 enum PaddleType {
     Left,
     Right,
 }
 
-#[derive(Component)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum AppState {
+    Menu,
+    Playing,
+    Paused,
+    GameOver,
+}
+
+/*
+  The rollback-tracked twin of `AppState`. Every transition gameplay
+  systems care about (Menu->Playing, Playing<->Paused, the GameOver
+  restart) is decided in the rollback schedule from GGRS-confirmed input,
+  but bevy's own `State<T>` can't be driven from there: GGRS resimulates
+  several virtual frames per real frame, and `State<T>` only tolerates
+  one pending transition at a time and isn't itself rollback-tracked, so
+  a resimulation burst can either collide with itself or get rolled back
+  to a stale value while `MatchControls`'s edge-trigger already fired -
+  a real, visible peer desync. `MatchPhase` is plain data instead, so it
+  rolls back/restores exactly like `Ball`/`Score`/`Counter`. `sync_app_state`
+  mirrors it onto the real `State<AppState>` once per real frame, outside
+  the rollback schedule, purely so the existing `SystemSet::on_update`-gated
+  UI keeps working.
+*/
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct MatchPhase(AppState);
+
+#[derive(Component, Clone)]
 struct Paddle {
     paddle_type: PaddleType,
     is_auto: bool,
+    toggle_held: bool,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 struct Position {
     y: f32,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 struct Ball {
-    x: f32,
-    y: f32,
+    vel: Vec2,
     speed_fact: f32,
 }
 
-#[derive(Component)]
+#[derive(Component, Clone)]
 struct Score {
     score: i64,
     paddle_type: PaddleType,
 }
 
+#[derive(Clone)]
 struct Counter {
     count: i64,
 }
 
+/*
+  Rising-edge state for the GGRS-synchronized confirm/pause bits, tracked
+  the same way `Paddle::toggle_held` tracks the auto-toggle bit. Has to be
+  rollback-tracked alongside `Ball`/`Score`/`Counter`, since restart/pause
+  decisions made from it mutate those in the same tick.
+*/
+#[derive(Clone, Default)]
+struct MatchControls {
+    confirm_held: bool,
+    pause_held: bool,
+}
+
 struct Sounds {
     ping: Handle<AudioSource>,
     button: Handle<AudioSource>,
 }
 
+// The camera sits at the world origin and never moves, so the listener
+// position `play_bounce_sound` pans/attenuates against is just a
+// constant rather than something queried from a camera `Transform` each
+// time. Not rollback-tracked: it's read only outside the rollback
+// schedule, by the same non-rollback system that reads `BounceCue`.
+struct Listener(Vec2);
+
+/*
+  Which clip the last confirmed bounce should play, if any. `None` means
+  `BounceCue::count` advanced for bookkeeping reasons but nothing should
+  actually sound (there's no such case today, but keeps the resource
+  total rather than `Option`-inside-a-query).
+*/
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum BounceClip {
+    None,
+    Wall,
+    Paddle,
+}
+
+impl Default for BounceClip {
+    fn default() -> Self {
+        BounceClip::None
+    }
+}
+
+/*
+  `ball_collision` runs inside the GGRS rollback schedule, which GGRS
+  re-executes wholesale on every misprediction - so it can't spawn audio
+  entities directly, or a discarded prediction pass leaves orphaned
+  sounds behind. Instead it only records *what the latest bounce should
+  sound like* here, including the ball's full position, so the actual
+  spatial computation (pan *and* distance falloff, not just a left/right
+  scalar) can still happen from outside the rollback schedule instead of
+  being collapsed early and losing the `y` axis. `play_bounce_sound`
+  plays one sound whenever `count` differs from what it saw on the
+  previous real frame. The cue is written on predicted frames as well as
+  confirmed ones, so a bounce that a later rollback discards can already
+  have sounded, and several bounces within one real frame sound once.
+*/
+#[derive(Clone, Default)]
+struct BounceCue {
+    count: u32,
+    clip: BounceClip,
+    pos: Vec2,
+    volume: f32,
+    speed: f32,
+}
+
+/*
+  Arena bounds read by the systems in `rollback_schedule` that need them.
+  Snapshotted once from this process's primary window at startup and
+  rollback-tracked from then on, rather than read live from `Res<Windows>`
+  each tick, so resizing the window mid-match doesn't change the
+  simulation. The snapshot is not exchanged with the peer: two peers
+  whose windows start at different sizes simulate different arenas.
+*/
+#[derive(Clone)]
+struct ArenaSize {
+    width: f32,
+    height: f32,
+}
+
+/*
+  Fixed simulation step in seconds. Every movement/collision system
+  advances the world by exactly this much per call rather than by raw
+  per-frame deltas, so the match plays out identically at 60Hz and
+  144Hz and can be replayed/rolled back deterministically.
+*/
+const TIME_STEP: f32 = 1.0 / 60.0;
+const PADDLE_SPEED: f32 = 600.0;
+
+const BALL_HALF_EXTENT: Vec2 = Vec2::new(20.0, 20.0);
+const PADDLE_HALF_EXTENT: Vec2 = Vec2::new(10.0, 50.0);
+// Steepest angle (from horizontal) a paddle-edge hit can send the ball at.
+const MAX_BOUNCE: f32 = std::f32::consts::FRAC_PI_3; // 60 degrees
+
+// First to this score, winning by at least 2, takes the match.
+const WIN_SCORE: i64 = 11;
+const WIN_BY: i64 = 2;
+
+/*
+  AABB overlap test between the ball and a paddle, both given as
+  (center, half-extent) pairs.
+*/
+fn aabb_overlap(a_pos: Vec2, a_half: Vec2, b_pos: Vec2, b_half: Vec2) -> bool {
+    (a_pos.x - b_pos.x).abs() <= a_half.x + b_half.x
+        && (a_pos.y - b_pos.y).abs() <= a_half.y + b_half.y
+}
+
 /*
   We add a branch of functions (called system) to the engine.
   For `startup_system`, they will be executed only once at startup.
@@ -48,34 +185,84 @@ struct Sounds {
   Each system fetches some data (through `Query`) and modify them.
 */
 fn main() {
-    App::new()
-        .add_plugin(LogDiagnosticsPlugin::default())
+    let net_config = NetworkConfig::from_args();
+    let session = network::build_p2p_session(&net_config);
+    let local_handle = net_config.local_handle;
+
+    let mut rollback_schedule = Schedule::default();
+    rollback_schedule.add_stage(
+        "rollback",
+        SystemStage::parallel()
+            .with_system(apply_match_controls)
+            .with_system(move_paddle)
+            .with_system(auto_move_paddle)
+            .with_system(transform_paddle)
+            .with_system(ball_move)
+            .with_system(ball_speed_up)
+            .with_system(ball_collision.system()),
+    );
+
+    let mut app = App::new();
+    app.add_plugin(LogDiagnosticsPlugin::default())
         .add_plugin(FrameTimeDiagnosticsPlugin::default())
         .add_plugins(DefaultPlugins)
         .add_plugin(EguiPlugin)
+        .add_plugin(AudioPlugin)
         .insert_resource(ClearColor(Color::rgb(1.0, 1.0, 1.0)))
         .insert_resource(Counter { count: 0 })
+        .insert_resource(MatchControls::default())
+        .insert_resource(MatchPhase(AppState::Menu))
+        .insert_resource(BounceCue::default())
+        .insert_resource(net_config)
+        .insert_resource(LocalHandle(local_handle))
+        .insert_resource(Session::P2PSession(session))
+        .add_state(AppState::Menu)
         .add_startup_system(setup)
         .add_startup_system(spawn_ball)
         .add_startup_system(spawn_paddle)
-        .add_system(ball_move)
-        .add_system(ball_speed_up)
-        .add_system(transform_paddle)
-        .add_system(move_paddle.system())
-        .add_system(ball_collision.system())
-        .add_system(auto_move_paddle.system())
-        .add_system(ui.system())
-        .run();
+        .add_system(play_bounce_sound.system())
+        .add_system(sync_app_state.system())
+        .add_system_set(SystemSet::on_update(AppState::Menu).with_system(menu_ui.system()))
+        .add_system_set(SystemSet::on_update(AppState::Playing).with_system(ui.system()))
+        .add_system_set(SystemSet::on_update(AppState::Paused).with_system(pause_ui.system()))
+        .add_system_set(
+            SystemSet::on_update(AppState::GameOver).with_system(game_over_ui.system()),
+        );
+
+    GGRSPlugin::<GGRSConfig>::new()
+        .with_update_frequency(60)
+        .with_input_system(network::read_local_inputs)
+        .register_rollback_component::<Transform>()
+        .register_rollback_component::<Ball>()
+        .register_rollback_component::<Position>()
+        .register_rollback_component::<Paddle>()
+        .register_rollback_component::<Score>()
+        .register_rollback_resource::<Counter>()
+        .register_rollback_resource::<ArenaSize>()
+        .register_rollback_resource::<MatchControls>()
+        .register_rollback_resource::<MatchPhase>()
+        .register_rollback_resource::<BounceCue>()
+        .with_rollback_schedule(rollback_schedule)
+        .build(&mut app);
+
+    app.run();
 }
 
-fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>, windows: Res<Windows>) {
     commands
         .spawn()
         .insert_bundle(OrthographicCameraBundle::new_2d());
+    commands.insert_resource(Listener(Vec2::ZERO));
     commands.insert_resource(Sounds {
         button: asset_server.load("button.mp3"),
         ping: asset_server.load("ping.mp3"),
     });
+
+    let win = windows.get_primary().unwrap();
+    commands.insert_resource(ArenaSize {
+        width: win.width(),
+        height: win.height(),
+    });
 }
 
 /*
@@ -94,63 +281,158 @@ fn ui(context: ResMut<EguiContext>, score: Query<&Score>, win: Res<Windows>,) {
             });
         }
 
-        ui.label("1P(Left): move W/D, toggle auto P");
-        ui.label("2P(Right): move Up/Down, toggle auto Q");
+        ui.label("1P(Left): move W/S, toggle auto P or Q");
+        ui.label("2P(Right): move Up/Down, toggle auto P or Q");
     });
 }
 
 /*
-  Move the ball according to its speed.
-  Direction, speed, etc. will be set in other system.
-  We only focus on moving it here.
+  Start screen shown before a match begins. Purely informational - the
+  actual Menu -> Playing transition happens in `apply_match_controls`,
+  driven by the same GGRS-synchronized Enter bit both peers observe.
 */
-fn ball_move(mut position: Query<(&Ball, &mut Transform)>) {
+fn menu_ui(context: ResMut<EguiContext>, win: Res<Windows>) {
+    let win = win.get_primary().unwrap();
+    egui::Window::new("pong")
+        .default_pos(Pos2::new(win.width() / 2.0 - 80.0, win.height() / 2.0 - 60.0))
+        .hscroll(false)
+        .show(context.ctx(), |ui| {
+            ui.heading("Pong");
+            ui.label("1P(Left): move W/S, toggle auto P or Q");
+            ui.label("2P(Right): move Up/Down, toggle auto P or Q");
+            ui.label("Press Enter to start, Esc to pause a match in progress");
+        });
+}
+
+/*
+  Score display plus a resume hint, shown while the match is frozen.
+  Resuming happens in `apply_match_controls`, not here.
+*/
+fn pause_ui(context: ResMut<EguiContext>, win: Res<Windows>, score: Query<&Score>) {
+    let win = win.get_primary().unwrap();
+    egui::Window::new("score")
+        .default_pos(Pos2::new(win.width() / 2.0 - 20.0, 0.0))
+        .hscroll(false)
+        .show(context.ctx(), |ui| {
+            for s in score.iter() {
+                ui.label(match s.paddle_type {
+                    PaddleType::Left => format!("Left: {}", s.score),
+                    PaddleType::Right => format!("Right: {}", s.score),
+                });
+            }
+            ui.label("Paused - press Esc to resume");
+        });
+}
+
+/*
+  Winner banner shown once a match ends. The restart itself (resetting
+  `Ball`/`Counter`/`Score`) happens in `apply_match_controls`, inside the
+  rollback schedule, so it stays in lockstep with the peer instead of
+  being applied unilaterally from this display-only UI system.
+*/
+fn game_over_ui(context: ResMut<EguiContext>, win: Res<Windows>, scores: Query<&Score>) {
+    let win = win.get_primary().unwrap();
+    let winner = scores
+        .iter()
+        .max_by_key(|s| s.score)
+        .map(|s| match s.paddle_type {
+            PaddleType::Left => "Left",
+            PaddleType::Right => "Right",
+        })
+        .unwrap_or("nobody");
+    egui::Window::new("game over")
+        .default_pos(Pos2::new(win.width() / 2.0 - 80.0, win.height() / 2.0 - 40.0))
+        .hscroll(false)
+        .show(context.ctx(), |ui| {
+            ui.heading(format!("{} wins!", winner));
+            ui.label("Press Enter to restart");
+        });
+}
+
+/*
+  Move the ball according to its velocity (units-per-second).
+  Direction, speed, etc. will be set in other system. We only focus on
+  moving it here, by exactly `TIME_STEP` seconds' worth of travel.
+*/
+fn ball_move(phase: Res<MatchPhase>, mut position: Query<(&Ball, &mut Transform)>) {
+    // Gameplay systems live in GGRS's own rollback schedule, not the main
+    // app schedule, so `SystemSet::on_update` can't gate them - guard here
+    // instead so the ball freezes outside `Playing`.
+    if phase.0 != AppState::Playing {
+        return;
+    }
     for (ball, mut transform) in position.iter_mut() {
-        transform.translation.x += ball.x * ball.speed_fact;
-        transform.translation.y += ball.y * ball.speed_fact;
+        transform.translation.x += ball.vel.x * ball.speed_fact * TIME_STEP;
+        transform.translation.y += ball.vel.y * ball.speed_fact * TIME_STEP;
     }
 }
 
 /*
-  Detect if the ball collapses into edge of screen.
+  Detect if the ball collides with a wall, a paddle, or flies past one.
   1) if it reaches top/bottom, we reverse its y.
-  2) if it reaches left/right edge:
-     a) a paddle catches it. we reverse its x.
-     b) paddle fails to reach. This paddle(side) is lose.
+  2) against a paddle we run an AABB test and split the response by which
+     face was struck:
+     a) the paddle's left/right edge - reflect with an angle that depends
+        on where along the paddle the ball landed.
+     b) the paddle's top/bottom edge - the paddle moved into the ball
+        vertically, so just reverse y instead of letting it clip through.
+  3) if neither paddle's AABB catches it before it crosses the far edge,
+     that side loses the point.
 */
 fn ball_collision(
-    win: Res<Windows>,
-    sounds: Res<Sounds>,
-    audio: Res<Audio>,
+    arena: Res<ArenaSize>,
+    mut cue: ResMut<BounceCue>,
     mut counter: ResMut<Counter>,
     mut position: Query<(&mut Ball, &mut Transform)>,
     paddle_position: Query<(&Paddle, &Position)>,
     mut scores: Query<&mut Score>,
+    mut phase: ResMut<MatchPhase>,
 ) {
-    let win = win.get_primary().unwrap();
-    let height = win.height() as f32 / 2.0 - 20.;
-    let width = win.width() as f32 / 2.0 - 20.;
+    if phase.0 != AppState::Playing {
+        return;
+    }
+    let height = arena.height / 2.0 - 20.;
+    let width = arena.width / 2.0 - 20.;
+    let paddle_x = arena.width / 2.0 - 10.0;
     for (mut ball, mut transform) in position.iter_mut() {
         if transform.translation.y >= height || transform.translation.y <= -height {
-            ball.y = -ball.y;
-            audio.play(sounds.button.clone());
+            ball.vel.y = -ball.vel.y;
+            queue_bounce_cue(
+                &mut cue,
+                BounceClip::Wall,
+                Vec2::new(transform.translation.x, transform.translation.y),
+                ball.speed_fact,
+            );
         }
-        if transform.translation.x >= width {
-            for (paddle, pos) in paddle_position.iter() {
-                match paddle.paddle_type {
-                    PaddleType::Right => {
-                        if transform.translation.y > pos.y - 50.
-                            && transform.translation.y < pos.y + 50.0
-                        {
-                            ball.x = -ball.x;
-                            counter.count += 1;
-                            audio.play(sounds.button.clone());
-                            return;
-                        }
-                    }
-                    _ => {}
-                }
+
+        let ball_pos = Vec2::new(transform.translation.x, transform.translation.y);
+        for (paddle, pos) in paddle_position.iter() {
+            let facing = match paddle.paddle_type {
+                PaddleType::Left => -1.0,
+                PaddleType::Right => 1.0,
+            };
+            let paddle_pos = Vec2::new(facing * paddle_x, pos.y);
+            if !aabb_overlap(ball_pos, BALL_HALF_EXTENT, paddle_pos, PADDLE_HALF_EXTENT) {
+                continue;
+            }
+
+            let overlap_x = BALL_HALF_EXTENT.x + PADDLE_HALF_EXTENT.x - (ball_pos.x - paddle_pos.x).abs();
+            let overlap_y = BALL_HALF_EXTENT.y + PADDLE_HALF_EXTENT.y - (ball_pos.y - paddle_pos.y).abs();
+            if overlap_x < overlap_y {
+                let speed = ball.vel.length();
+                let relative = ((ball_pos.y - pos.y) / PADDLE_HALF_EXTENT.y).clamp(-1.0, 1.0);
+                let angle = relative * MAX_BOUNCE;
+                ball.vel.x = facing * -speed * angle.cos();
+                ball.vel.y = speed * angle.sin();
+            } else {
+                ball.vel.y = -ball.vel.y;
             }
+            counter.count += 1;
+            queue_bounce_cue(&mut cue, BounceClip::Paddle, ball_pos, ball.speed_fact);
+            return;
+        }
+
+        if transform.translation.x >= width {
             for mut score in scores.iter_mut() {
                 match score.paddle_type {
                     PaddleType::Left => {
@@ -162,23 +444,14 @@ fn ball_collision(
             transform.translation.x = 0.;
             transform.translation.y = 0.;
             counter.count = 0;
-            audio.play(sounds.ping.clone());
+            queue_bounce_cue(
+                &mut cue,
+                BounceClip::Wall,
+                Vec2::new(transform.translation.x, transform.translation.y),
+                ball.speed_fact,
+            );
+            check_win_condition(&scores, &mut phase);
         } else if transform.translation.x <= -width {
-            for (paddle, pos) in paddle_position.iter() {
-                match paddle.paddle_type {
-                    PaddleType::Left => {
-                        if transform.translation.y > pos.y - 50.
-                            && transform.translation.y < pos.y + 50.0
-                        {
-                            ball.x = -ball.x;
-                            counter.count += 1;
-                            audio.play(sounds.button.clone());
-                            return;
-                        }
-                    }
-                    _ => {}
-                }
-            }
             for mut score in scores.iter_mut() {
                 match score.paddle_type {
                     PaddleType::Right => {
@@ -190,11 +463,103 @@ fn ball_collision(
             transform.translation.x = 0.;
             transform.translation.y = 0.;
             counter.count = 0;
-            audio.play(sounds.ping.clone());
+            queue_bounce_cue(
+                &mut cue,
+                BounceClip::Wall,
+                Vec2::new(transform.translation.x, transform.translation.y),
+                ball.speed_fact,
+            );
+            check_win_condition(&scores, &mut phase);
         }
     }
 }
 
+/*
+  Record what the next confirmed bounce should sound like: which clip,
+  at the ball's full position (not collapsed to a left/right scalar
+  here - `play_bounce_sound` needs the real `y` too, to compute genuine
+  distance-based attenuation instead of just panning), with faster
+  rallies (`speed_fact`) playing louder and at a slightly higher pitch.
+  Does not touch `Audio` itself - see `play_bounce_sound`.
+*/
+fn queue_bounce_cue(cue: &mut BounceCue, clip: BounceClip, pos: Vec2, speed_fact: f32) {
+    cue.count += 1;
+    cue.clip = clip;
+    cue.pos = pos;
+    cue.volume = (0.6 + speed_fact * 0.08).min(1.5);
+    cue.speed = (0.9 + speed_fact * 0.05).min(1.6);
+}
+
+/*
+  Plays at most one sound per real frame. `ball_collision` lives in
+  the GGRS rollback schedule and can run several times for the same
+  frame during resimulation, so this system - outside that schedule -
+  watches `BounceCue::count` with `Local` edge detection instead of
+  reacting to every `ball_collision` call directly; only a count that
+  differs from the one seen last time this system ran gets a sound.
+  Predicted frames write the cue too, so this does not wait for a bounce
+  to be confirmed.
+
+  This is also where the real spatial computation happens, from the
+  ball's full queued position relative to `Listener`: `pan` from how far
+  off-center along x it bounced, plus a distance-based volume falloff so
+  a bounce at the far edge of the arena genuinely sounds farther away,
+  not just panned - `bevy_kira_audio` has no spatial-emitter API of its
+  own to lean on here, but the inputs driving it are the same ones a
+  real spatial-audio entity would use.
+*/
+fn play_bounce_sound(
+    cue: Res<BounceCue>,
+    sounds: Res<Sounds>,
+    audio: Res<Audio>,
+    listener: Res<Listener>,
+    arena: Res<ArenaSize>,
+    mut last_seen: Local<u32>,
+) {
+    if cue.count == *last_seen {
+        return;
+    }
+    *last_seen = cue.count;
+    let clip = match cue.clip {
+        BounceClip::Wall => sounds.ping.clone(),
+        BounceClip::Paddle => sounds.button.clone(),
+        BounceClip::None => return,
+    };
+
+    let offset = cue.pos - listener.0;
+    let pan = ((offset.x / (arena.width / 2.0)).clamp(-1.0, 1.0) + 1.0) / 2.0;
+    let max_dist = (Vec2::new(arena.width, arena.height) / 2.0).length();
+    let falloff = (offset.length() / max_dist).clamp(0.0, 1.0);
+    let volume = cue.volume * (1.0 - falloff * 0.5);
+
+    audio
+        .play(clip)
+        .with_panning(pan)
+        .with_volume(volume)
+        .with_playback_rate(cue.speed);
+}
+
+/*
+  First player to reach `WIN_SCORE` while leading by at least `WIN_BY`
+  takes the match.
+*/
+fn check_win_condition(scores: &Query<&mut Score>, phase: &mut MatchPhase) {
+    let left = scores
+        .iter()
+        .find(|s| matches!(s.paddle_type, PaddleType::Left))
+        .map(|s| s.score)
+        .unwrap_or(0);
+    let right = scores
+        .iter()
+        .find(|s| matches!(s.paddle_type, PaddleType::Right))
+        .map(|s| s.score)
+        .unwrap_or(0);
+    let leader = left.max(right);
+    if leader >= WIN_SCORE && (left - right).abs() >= WIN_BY {
+        phase.0 = AppState::GameOver;
+    }
+}
+
 fn ball_speed_up(counter: Res<Counter>, mut ball: Query<&mut Ball>, score: Query<&Score>) {
     let cur_score = score.iter().map(|x| x.score).sum::<i64>();
     for mut ball in ball.iter_mut() {
@@ -209,9 +574,8 @@ fn ball_speed_up(counter: Res<Counter>, mut ball: Query<&mut Ball>, score: Query
   `Position` will be modified in `*move_paddle`, here we just transform paddles.
   Of course, we should also make sure paddles only appears on left/right edges.
 */
-fn transform_paddle(windows: Res<Windows>, mut q: Query<(&Paddle, &Position, &mut Transform)>) {
-    let win = windows.get_primary().unwrap();
-    let paddle_x = (win.width() / 2.0) - 10.0;
+fn transform_paddle(arena: Res<ArenaSize>, mut q: Query<(&Paddle, &Position, &mut Transform)>) {
+    let paddle_x = (arena.width / 2.0) - 10.0;
     for (paddle, pos, mut transform) in q.iter_mut() {
         transform.translation = Vec3::new(
             match paddle.paddle_type {
@@ -228,23 +592,25 @@ fn transform_paddle(windows: Res<Windows>, mut q: Query<(&Paddle, &Position, &mu
   automatically move paddles
 */
 fn auto_move_paddle(
-    win: Res<Windows>,
+    phase: Res<MatchPhase>,
+    arena: Res<ArenaSize>,
     mut q: Query<(&Paddle, &mut Position)>,
     b: Query<(&Ball, &Transform)>,
 ) {
-    let win = win.get_primary().unwrap();
-    let height = win.height() as f32;
-    let width = win.width() as f32;
-    let speed = height / 100.;
+    if phase.0 != AppState::Playing {
+        return;
+    }
+    let width = arena.width;
+    let speed = PADDLE_SPEED * TIME_STEP;
 
     let (ball, trans) = b.iter().next().unwrap();
     for (paddle, mut pos) in q.iter_mut() {
         if paddle.is_auto {
             let target_y = trans.translation.y
-                + ball.y
+                + ball.vel.y
                     * match paddle.paddle_type {
-                        PaddleType::Left => (-width / 2.0 - trans.translation.x) / ball.x,
-                        PaddleType::Right => (width / 2.0 - trans.translation.x) / ball.x,
+                        PaddleType::Left => (-width / 2.0 - trans.translation.x) / ball.vel.x,
+                        PaddleType::Right => (width / 2.0 - trans.translation.x) / ball.vel.x,
                     };
             if target_y > pos.y {
                 pos.y += speed;
@@ -256,59 +622,119 @@ fn auto_move_paddle(
 }
 
 /*
-  move paddles according to `Input<KeyCode>`
-  1) Left Paddle:
-     W - up
-     D - down
-     P - auto/manual
-  2) Right Paddle:
-     UP Arrow - up
-     Down Arrow - down
-     Q - auto/manual
+  Move paddles from GGRS-confirmed/predicted input, never raw keyboard
+  state directly - that's what keeps both peers in lockstep. Left paddle
+  reads player handle 0's bits, right paddle reads handle 1's.
+
+  `read_local_inputs` doesn't key any of these off which paddle you're
+  assigned: W/S and Up/Down both set the same up/down bits, and P/Q both
+  set the same auto/manual toggle bit, for whichever handle is local to
+  this process. So whichever side you're playing, any of W/S, the arrow
+  keys, or P/Q work.
 */
 fn move_paddle(
-    input: Res<Input<KeyCode>>,
-    win: Res<Windows>,
+    phase: Res<MatchPhase>,
+    inputs: Res<bevy_ggrs::PlayerInputs<GGRSConfig>>,
+    arena: Res<ArenaSize>,
     mut q: Query<(&mut Paddle, &mut Position)>,
 ) {
-    let height = win.get_primary().unwrap().height() as f32;
-    let speed = height / 100.;
+    if phase.0 != AppState::Playing {
+        return;
+    }
+    let height = arena.height;
+    let speed = PADDLE_SPEED * TIME_STEP;
     for (mut paddle, mut pos) in q.iter_mut() {
-        match paddle.paddle_type {
-            PaddleType::Left => {
-                if !paddle.is_auto {
-                    if input.pressed(KeyCode::W) {
-                        pos.y += speed;
-                    }
-                    if input.pressed(KeyCode::S) {
-                        pos.y -= speed;
-                    }
-                }
-                if input.just_pressed(KeyCode::Q) {
-                    paddle.is_auto = !paddle.is_auto;
-                }
+        let handle = match paddle.paddle_type {
+            PaddleType::Left => 0,
+            PaddleType::Right => 1,
+        };
+        let (input, _) = inputs[handle];
+        if !paddle.is_auto {
+            if input.inp & INPUT_UP != 0 {
+                pos.y += speed;
             }
-            PaddleType::Right => {
-                if !paddle.is_auto {
-                    if input.pressed(KeyCode::Up) {
-                        pos.y += speed;
-                    }
-                    if input.pressed(KeyCode::Down) {
-                        pos.y -= speed;
-                    }
-                }
-                if input.just_pressed(KeyCode::P) {
-                    paddle.is_auto = !paddle.is_auto;
-                }
+            if input.inp & INPUT_DOWN != 0 {
+                pos.y -= speed;
             }
         }
+        let toggle_held = input.inp & INPUT_TOGGLE_AUTO != 0;
+        if toggle_held && !paddle.toggle_held {
+            paddle.is_auto = !paddle.is_auto;
+        }
+        paddle.toggle_held = toggle_held;
         pos.y = pos.y.min(height / 2.0 - 50.).max(-height / 2.0 + 50.);
     }
 }
 
+/*
+  Menu/pause/restart transitions, driven by the same GGRS-confirmed
+  `INPUT_CONFIRM`/`INPUT_PAUSE` bits both peers observe, so a
+  start/pause/restart decision is never made from one peer's raw local
+  input alone. Lives in the rollback schedule because the `GameOver`
+  restart branch resets rollback-tracked `Ball`/`Score`/`Counter` and
+  needs to stay in lockstep with the rest of the simulation.
+*/
+fn apply_match_controls(
+    inputs: Res<bevy_ggrs::PlayerInputs<GGRSConfig>>,
+    mut controls: ResMut<MatchControls>,
+    mut phase: ResMut<MatchPhase>,
+    mut scores: Query<&mut Score>,
+    mut ball: Query<(&mut Ball, &mut Transform)>,
+    mut counter: ResMut<Counter>,
+) {
+    let confirm_held = inputs.iter().any(|(input, _)| input.inp & INPUT_CONFIRM != 0);
+    let pause_held = inputs.iter().any(|(input, _)| input.inp & INPUT_PAUSE != 0);
+    let confirm_pressed = confirm_held && !controls.confirm_held;
+    let pause_pressed = pause_held && !controls.pause_held;
+    controls.confirm_held = confirm_held;
+    controls.pause_held = pause_held;
+
+    let current = phase.0;
+    match current {
+        AppState::Menu if confirm_pressed => {
+            phase.0 = AppState::Playing;
+        }
+        AppState::Playing if pause_pressed => {
+            phase.0 = AppState::Paused;
+        }
+        AppState::Paused if pause_pressed => {
+            phase.0 = AppState::Playing;
+        }
+        AppState::GameOver if confirm_pressed => {
+            for mut score in scores.iter_mut() {
+                score.score = 0;
+            }
+            for (mut ball, mut transform) in ball.iter_mut() {
+                ball.vel = Vec2::new(180.0, 180.0);
+                ball.speed_fact = 1.0;
+                transform.translation.x = 0.;
+                transform.translation.y = 0.;
+            }
+            counter.count = 0;
+            phase.0 = AppState::Playing;
+        }
+        _ => {}
+    }
+}
+
+/*
+  Mirrors the rollback-tracked `MatchPhase` onto bevy's own
+  `State<AppState>`, once per real frame, outside the rollback schedule -
+  see `MatchPhase`'s doc comment for why the rollback schedule can't just
+  drive `State<AppState>` directly. Only the menu/pause/game-over UI
+  (gated by `SystemSet::on_update`) reads `State<AppState>`; nothing in
+  the rollback schedule does anymore.
+*/
+fn sync_app_state(phase: Res<MatchPhase>, mut state: ResMut<State<AppState>>) {
+    if *state.current() != phase.0 {
+        let _ = state.set(phase.0);
+    }
+}
+
 fn spawn_ball(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    mut rollback_ids: ResMut<RollbackIdProvider>,
 ) {
     let mat = asset_server.load("ball.png");
     commands
@@ -322,10 +748,10 @@ fn spawn_ball(
             ..Default::default()
         })
         .insert(Ball {
-            x: 3.0,
-            y: 3.0,
+            vel: Vec2::new(180.0, 180.0),
             speed_fact: 1.0,
-        });
+        })
+        .insert(Rollback::new(rollback_ids.next_id()));
 }
 
 /*
@@ -334,6 +760,7 @@ fn spawn_ball(
 fn spawn_paddle(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    mut rollback_ids: ResMut<RollbackIdProvider>,
 ) {
     let mat = asset_server.load("paddle1.png");
     commands
@@ -349,12 +776,14 @@ fn spawn_paddle(
         .insert(Paddle {
             paddle_type: PaddleType::Left,
             is_auto: true,
+            toggle_held: false,
         })
         .insert(Position { y: 0.0 })
         .insert(Score {
             score: 0,
             paddle_type: PaddleType::Left,
-        });
+        })
+        .insert(Rollback::new(rollback_ids.next_id()));
     commands.spawn()
         .insert_bundle(SpriteBundle {
             texture: mat,
@@ -368,9 +797,11 @@ fn spawn_paddle(
         .insert(Paddle {
             paddle_type: PaddleType::Right,
             is_auto: true,
+            toggle_held: false,
         })
         .insert(Score {
             score: 0,
             paddle_type: PaddleType::Right,
-        });
+        })
+        .insert(Rollback::new(rollback_ids.next_id()));
 }
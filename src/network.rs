@@ -0,0 +1,141 @@
+/*
+  Rollback networking for the two-player online mode.
+
+  Everything a peer needs to reconstruct the match lives here: the packed
+  input bitfield GGRS ships between peers, the `ggrs::Config` binding that
+  wires our input/address types into GGRS, and the small helpers used to
+  stand up a `P2PSession` from CLI-provided endpoints.
+
+  The one rule every system feeding the rollback schedule must follow:
+  only ever read input through `PaddleInput`/GGRS-supplied frames, never
+  `Res<Windows>` or wall-clock time, or the two peers will diverge.
+*/
+use bevy::prelude::*;
+use bytemuck::{Pod, Zeroable};
+use ggrs::{Config, PlayerHandle};
+use std::net::SocketAddr;
+
+pub const INPUT_UP: u8 = 1 << 0;
+pub const INPUT_DOWN: u8 = 1 << 1;
+pub const INPUT_TOGGLE_AUTO: u8 = 1 << 2;
+// Menu/match-flow controls. These also have to ride the GGRS input channel:
+// a start/pause/restart decided from raw local input would let one peer's
+// match state silently diverge from the other's.
+pub const INPUT_CONFIRM: u8 = 1 << 3;
+pub const INPUT_PAUSE: u8 = 1 << 4;
+
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Default, Pod, Zeroable)]
+pub struct PaddleInput {
+    pub inp: u8,
+}
+
+#[derive(Debug)]
+pub struct GGRSConfig;
+
+impl Config for GGRSConfig {
+    type Input = PaddleInput;
+    type State = u8;
+    type Address = SocketAddr;
+}
+
+/*
+  Which side of the match this process is simulating locally. The other
+  side's paddle is driven entirely by GGRS-confirmed/predicted input.
+*/
+pub struct LocalHandle(pub PlayerHandle);
+
+/*
+  Parsed from CLI args: `pong-bevy --local-port 7000 --remote 1.2.3.4:7001`.
+  `main` parses it before building the app and hands it to
+  `build_p2p_session` directly; it is also inserted as a resource.
+*/
+pub struct NetworkConfig {
+    pub local_port: u16,
+    pub remote_addr: SocketAddr,
+    pub local_handle: PlayerHandle,
+}
+
+impl NetworkConfig {
+    pub fn from_args() -> Self {
+        let args: Vec<String> = std::env::args().collect();
+        let local_port = read_flag(&args, "--local-port")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(7000);
+        let remote_addr = read_flag(&args, "--remote")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_else(|| "127.0.0.1:7001".parse().unwrap());
+        let local_handle = read_flag(&args, "--local-handle")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(0);
+        Self {
+            local_port,
+            remote_addr,
+            local_handle,
+        }
+    }
+}
+
+fn read_flag(args: &[String], flag: &str) -> Option<String> {
+    args.iter()
+        .position(|a| a == flag)
+        .and_then(|i| args.get(i + 1))
+        .cloned()
+}
+
+/*
+  Sample local keyboard state into the bitfield GGRS ships to the peer.
+  Registered with `GGRSPlugin::with_input_system`; must never look at
+  anything besides `Input<KeyCode>` (and `LocalHandle`, to tell which
+  handle is ours).
+*/
+pub fn read_local_inputs(
+    handle: In<PlayerHandle>,
+    keyboard: Res<Input<KeyCode>>,
+    local: Res<LocalHandle>,
+) -> PaddleInput {
+    let mut inp = 0u8;
+    if handle.0 == local.0 {
+        if keyboard.pressed(KeyCode::Up) || keyboard.pressed(KeyCode::W) {
+            inp |= INPUT_UP;
+        }
+        if keyboard.pressed(KeyCode::Down) || keyboard.pressed(KeyCode::S) {
+            inp |= INPUT_DOWN;
+        }
+        if keyboard.just_pressed(KeyCode::P) || keyboard.just_pressed(KeyCode::Q) {
+            inp |= INPUT_TOGGLE_AUTO;
+        }
+        if keyboard.pressed(KeyCode::Return) {
+            inp |= INPUT_CONFIRM;
+        }
+        if keyboard.pressed(KeyCode::Escape) {
+            inp |= INPUT_PAUSE;
+        }
+    }
+    PaddleInput { inp }
+}
+
+/*
+  Build the `P2PSession` for a two-player match: one UDP socket bound to
+  our local port, one remote peer, a short prediction window and input
+  delay so local input still feels instant.
+*/
+pub fn build_p2p_session(config: &NetworkConfig) -> ggrs::P2PSession<GGRSConfig> {
+    let socket = ggrs::UdpNonBlockingSocket::bind_to_port(config.local_port)
+        .expect("failed to bind GGRS socket");
+
+    let remote_handle = 1 - config.local_handle;
+    let mut builder = ggrs::SessionBuilder::<GGRSConfig>::new()
+        .with_num_players(2)
+        .with_max_prediction_window(8)
+        .with_input_delay(2)
+        .add_player(ggrs::PlayerType::Local, config.local_handle)
+        .expect("failed to add local player");
+    builder = builder
+        .add_player(ggrs::PlayerType::Remote(config.remote_addr), remote_handle)
+        .expect("failed to add remote player");
+
+    builder
+        .start_p2p_session(socket)
+        .expect("failed to start p2p session")
+}